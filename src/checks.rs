@@ -1,5 +1,7 @@
 use crate::notebook::{Cell, Notebook};
 use enum_dispatch::enum_dispatch;
+use serde::Serialize;
+use std::collections::HashMap;
 use std::string::ToString;
 extern crate strsim;
 use strsim::levenshtein;
@@ -15,12 +17,31 @@ trait CheckTrait {
 }
 
 #[enum_dispatch(CheckTrait)]
-#[derive(Debug, PartialEq, Clone, EnumString, Display, Sequence)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, EnumString, Display, Sequence)]
 pub enum Check {
     FileNotNamedUntitled,
     CellExecutionIsSequential,
     NoEmptyCells,
     HasTitleCell,
+    MarkdownCodeBlocksValid,
+}
+
+/// How much a failing check should matter: `Error` fails the run,
+/// `Warning` is printed but doesn't, and `Off` is equivalent to disabling
+/// the check. Defaults to `Error` for any check not named in the
+/// `[tool.nbsanity] severity` table.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, EnumString, Display, Serialize)]
+#[strum(serialize_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+    Off,
+}
+
+impl Default for Severity {
+    fn default() -> Self {
+        Severity::Error
+    }
 }
 
 impl Check {
@@ -29,6 +50,33 @@ impl Check {
 }
 }
 
+impl Serialize for Check {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+/// What happened when a `Fix` was asked to repair a notebook.
+#[derive(Debug, PartialEq)]
+pub enum FixOutcome {
+    /// The notebook was mutated in place and should be written back to disk.
+    Applied,
+    /// No automatic fix exists; this message should be shown to the user instead.
+    Suggested(String),
+    /// Nothing to do, the check already passes.
+    Clean,
+}
+
+/// Companion to `CheckTrait`: given a notebook that failed a check, either
+/// repair it in place or explain why it can't be repaired automatically.
+#[enum_dispatch(Check)]
+pub trait Fix {
+    fn fix(&self, notebook: &mut Notebook) -> FixOutcome;
+}
+
 pub fn find_closest(s: String) -> Check {
     let checks = Check::all();
     let closest = checks.iter()
@@ -38,7 +86,7 @@ pub fn find_closest(s: String) -> Check {
     closest.1.clone()
 }
 
-#[derive(Debug, PartialEq, Clone, Default, Sequence)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default, Sequence)]
 pub struct FileNotNamedUntitled;
 
 impl CheckTrait for FileNotNamedUntitled {
@@ -51,20 +99,40 @@ impl CheckTrait for FileNotNamedUntitled {
     }
 }
 
-#[derive(Debug, PartialEq, Clone, Default, Sequence)]
+impl Fix for FileNotNamedUntitled {
+    fn fix(&self, notebook: &mut Notebook) -> FixOutcome {
+        // Renaming the file on disk is a decision nbsanity shouldn't make
+        // for the user, so this only ever suggests a new name.
+        if notebook.filename_str().to_lowercase().contains("untitled") {
+            FixOutcome::Suggested(format!(
+                "rename {} to something that describes its contents",
+                notebook.filename_str()
+            ))
+        } else {
+            FixOutcome::Clean
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default, Sequence)]
 pub struct CellExecutionIsSequential;
 impl CheckTrait for CellExecutionIsSequential {
     fn check(&self, notebook: &Notebook) -> AnalysisResult {
         let mut result = AnalysisResult::new(Check::CellExecutionIsSequential(self.clone()));
-        for (previous, cell) in (0_i32..).zip(notebook.code_cells().iter()) {
+        // Only cells that were actually run count toward the expected
+        // sequence, so this matches Fix's renumbering scheme exactly:
+        // never-run cells are reported but don't shift later expectations.
+        let mut expected = 1;
+        for cell in notebook.code_cells() {
             match cell.execution_count {
                 Some(count) => {
-                    if count != previous + 1 {
+                    if count != expected {
                         result.add_failure(
                             cell.idx.unwrap_or(std::usize::MAX),
                             format!("Not executed in order, got {}", count),
                         )
                     }
+                    expected += 1;
                 }
                 None => result.add_failure(
                     cell.idx.unwrap_or(std::usize::MAX),
@@ -76,7 +144,33 @@ impl CheckTrait for CellExecutionIsSequential {
     }
 }
 
-#[derive(Debug, PartialEq, Clone, Default, Sequence)]
+impl Fix for CellExecutionIsSequential {
+    fn fix(&self, notebook: &mut Notebook) -> FixOutcome {
+        let mut changed = false;
+        let mut next = 1;
+        for cell in notebook.cells.iter_mut() {
+            if let Cell::Code(c) = cell {
+                // A cell that was never run has no execution history to
+                // renumber; stamping one would fabricate it.
+                if c.execution_count.is_none() {
+                    continue;
+                }
+                if c.execution_count != Some(next) {
+                    c.execution_count = Some(next);
+                    changed = true;
+                }
+                next += 1;
+            }
+        }
+        if changed {
+            FixOutcome::Applied
+        } else {
+            FixOutcome::Clean
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default, Sequence)]
 pub struct NoEmptyCells;
 impl CheckTrait for NoEmptyCells {
     fn check(&self, notebook: &Notebook) -> AnalysisResult {
@@ -110,7 +204,26 @@ impl CheckTrait for NoEmptyCells {
     }
 }
 
-#[derive(Debug, PartialEq, Clone, Default, Sequence)]
+impl Fix for NoEmptyCells {
+    fn fix(&self, notebook: &mut Notebook) -> FixOutcome {
+        let before = notebook.cells.len();
+        notebook.cells.retain(|cell| {
+            let source = match cell {
+                Cell::Code(c) => &c.source,
+                Cell::Markdown(c) => &c.source,
+            };
+            !(source.is_empty() || source.iter().all(|s| s.trim().is_empty()))
+        });
+        if notebook.cells.len() != before {
+            notebook.add_cell_indices();
+            FixOutcome::Applied
+        } else {
+            FixOutcome::Clean
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default, Sequence)]
 pub struct HasTitleCell;
 impl CheckTrait for HasTitleCell {
     fn check(&self, notebook: &Notebook) -> AnalysisResult {
@@ -130,15 +243,106 @@ impl CheckTrait for HasTitleCell {
     }
 }
 
-#[derive(Debug)]
+impl Fix for HasTitleCell {
+    fn fix(&self, notebook: &mut Notebook) -> FixOutcome {
+        if self.check(notebook).pass() {
+            return FixOutcome::Clean;
+        }
+        // Writing a meaningful title is not something nbsanity can guess.
+        FixOutcome::Suggested("add a markdown title cell, e.g. '# My Notebook'".to_string())
+    }
+}
+
+/// The language tag and `ignore` attribute parsed out of a fence info
+/// string, e.g. "```python ignore" -> (Some("python"), true). Mirrors the
+/// attribute parsing rustdoc does on markdown code fences.
+fn parse_fence_info(info: &str) -> (Option<String>, bool) {
+    let mut tokens = info
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|s| !s.is_empty());
+    let lang = tokens.next().map(|s| s.to_lowercase());
+    let ignore = lang.as_deref() == Some("ignore") || tokens.any(|t| t.eq_ignore_ascii_case("ignore"));
+    let lang = match lang.as_deref() {
+        Some("ignore") => None,
+        _ => lang,
+    };
+    (lang, ignore)
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default, Sequence)]
+pub struct MarkdownCodeBlocksValid;
+impl CheckTrait for MarkdownCodeBlocksValid {
+    fn check(&self, notebook: &Notebook) -> AnalysisResult {
+        let mut result = AnalysisResult::new(Check::MarkdownCodeBlocksValid(self.clone()));
+        let notebook_lang = notebook.language_name();
+
+        for cell in notebook.markdown_cells() {
+            let mut open_fence: Option<(Option<String>, bool)> = None;
+            for line in &cell.source {
+                let trimmed = line.trim_start();
+                if !trimmed.starts_with("```") {
+                    continue;
+                }
+                match open_fence.take() {
+                    None => {
+                        let (lang, ignore) = parse_fence_info(trimmed[3..].trim());
+                        if !ignore {
+                            if let (Some(lang), Some(notebook_lang)) = (&lang, notebook_lang) {
+                                if !lang.eq_ignore_ascii_case(notebook_lang) {
+                                    result.add_failure(
+                                        cell.idx.unwrap_or(std::usize::MAX),
+                                        format!(
+                                            "Code fence declares language '{}' but notebook kernel is '{}'",
+                                            lang, notebook_lang
+                                        ),
+                                    );
+                                }
+                            }
+                        }
+                        open_fence = Some((lang, ignore));
+                    }
+                    Some(_) => {
+                        // Closing fence found, nothing left open.
+                    }
+                }
+            }
+
+            if open_fence.is_some() {
+                result.add_failure(
+                    cell.idx.unwrap_or(std::usize::MAX),
+                    "Unterminated code fence in markdown cell".to_string(),
+                )
+            }
+        }
+
+        result
+    }
+}
+
+impl Fix for MarkdownCodeBlocksValid {
+    fn fix(&self, notebook: &mut Notebook) -> FixOutcome {
+        if self.check(notebook).pass() {
+            return FixOutcome::Clean;
+        }
+        // Closing a fence or retagging its language requires knowing what
+        // the author meant, which nbsanity can't guess.
+        FixOutcome::Suggested(
+            "close unterminated fences and fix code fence language tags by hand".to_string(),
+        )
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
 struct ResultFailure {
     cell_id: usize,
     description: String,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct AnalysisResult {
     check: Check,
+    #[serde(default)]
+    severity: Severity,
     failures: Vec<ResultFailure>,
 }
 
@@ -151,6 +355,10 @@ impl AnalysisResult {
         self.failures.is_empty()
     }
 
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
     fn add_failure(&mut self, cell_id: usize, description: String) {
         self.failures.push(ResultFailure {
             cell_id,
@@ -161,36 +369,265 @@ impl AnalysisResult {
     fn new(check: Check) -> Self {
         AnalysisResult {
             check,
+            severity: Severity::default(),
             failures: vec![],
         }
     }
 }
 
-pub fn analyze(notebook: &Notebook, exclude: &[Check]) -> Vec<AnalysisResult> {
-    Check::all().iter()
+pub fn analyze(
+    notebook: &Notebook,
+    exclude: &[Check],
+    severity: &HashMap<Check, Severity>,
+) -> Vec<AnalysisResult> {
+    Check::all()
+        .iter()
         .filter(|c| !exclude.contains(c))
-        .map(|c| c.check(notebook))
+        .map(|c| {
+            let mut result = c.check(notebook);
+            result.severity = severity.get(c).copied().unwrap_or_default();
+            result
+        })
         .collect()
 }
 
+/// Whether any `Severity::Error` check failed. Warnings are reported but
+/// never flip the exit status on their own; see `warning_count` for
+/// `--max-warnings`.
 pub fn any_failed(results: &[AnalysisResult]) -> bool {
-    results.iter().any(|r| !r.pass())
+    results
+        .iter()
+        .any(|r| !r.pass() && r.severity == Severity::Error)
+}
+
+/// Number of individual findings from `Severity::Warning` checks, for
+/// `--max-warnings`.
+pub fn warning_count(results: &[AnalysisResult]) -> usize {
+    results
+        .iter()
+        .filter(|r| r.severity == Severity::Warning)
+        .map(|r| r.failures.len())
+        .sum()
+}
+
+/// Run every fixable check's `Fix` against `notebook`, mutating it in
+/// place for anything that was `Applied`. Returns the outcome of each
+/// check so the caller can decide whether to write the notebook back to
+/// disk and what to print for `Suggested` fixes.
+pub fn fix_all(notebook: &mut Notebook, exclude: &[Check]) -> Vec<(Check, FixOutcome)> {
+    Check::all()
+        .into_iter()
+        .filter(|c| !exclude.contains(c))
+        .map(|c| {
+            let outcome = c.fix(notebook);
+            (c, outcome)
+        })
+        .collect()
+}
+
+/// The output format a `Reporter` produces, selected on the CLI via
+/// `--format`.
+#[derive(Debug, Clone, Copy, EnumString, Display)]
+#[strum(serialize_all = "lowercase")]
+pub enum ReportFormat {
+    Human,
+    Json,
+    Sarif,
+}
+
+/// Something that can turn per-notebook `AnalysisResult`s into output.
+/// `report` is called once per notebook after it's analyzed; `finish` is
+/// called once at the end so reporters that need a single root document
+/// (JSON, SARIF) can flush everything they've collected.
+pub trait Reporter {
+    fn report(&mut self, results: &[AnalysisResult], notebook: &Notebook);
+
+    fn finish(&mut self) {}
+}
+
+pub fn reporter_for(format: ReportFormat, quiet: bool) -> Box<dyn Reporter> {
+    match format {
+        ReportFormat::Human => Box::new(HumanReporter { quiet }),
+        ReportFormat::Json => Box::new(JsonReporter::default()),
+        ReportFormat::Sarif => Box::new(SarifReporter::default()),
+    }
+}
+
+pub struct HumanReporter {
+    quiet: bool,
+}
+
+impl Reporter for HumanReporter {
+    fn report(&mut self, results: &[AnalysisResult], notebook: &Notebook) {
+        if results.iter().any(|r| !r.pass()) {
+            for r in results.iter() {
+                for failure in &r.failures {
+                    println!(
+                        "{} <Cell: {}> {} [{}, {}]",
+                        notebook.filename_str(),
+                        failure.cell_id,
+                        failure.description,
+                        r.check,
+                        r.severity
+                    )
+                }
+            }
+        } else if !self.quiet {
+            println!("{} \u{2705}", notebook.filename_str());
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct JsonFinding {
+    check: Check,
+    severity: Severity,
+    filename: String,
+    pass: bool,
+    failures: Vec<ResultFailure>,
+}
+
+#[derive(Default)]
+pub struct JsonReporter {
+    findings: Vec<JsonFinding>,
+}
+
+impl Reporter for JsonReporter {
+    fn report(&mut self, results: &[AnalysisResult], notebook: &Notebook) {
+        for r in results.iter() {
+            self.findings.push(JsonFinding {
+                check: r.check.clone(),
+                severity: r.severity,
+                filename: notebook.filename_str().to_string(),
+                pass: r.pass(),
+                failures: r.failures.clone(),
+            });
+        }
+    }
+
+    fn finish(&mut self) {
+        println!("{}", serde_json::to_string_pretty(&self.findings).unwrap());
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct SarifArtifactLocation {
+    uri: String,
+}
+
+#[derive(Debug, Serialize)]
+struct SarifRegion {
+    #[serde(rename = "startLine")]
+    start_line: usize,
+}
+
+#[derive(Debug, Serialize)]
+struct SarifPhysicalLocation {
+    #[serde(rename = "artifactLocation")]
+    artifact_location: SarifArtifactLocation,
+    region: SarifRegion,
+}
+
+#[derive(Debug, Serialize)]
+struct SarifLocation {
+    #[serde(rename = "physicalLocation")]
+    physical_location: SarifPhysicalLocation,
+}
+
+#[derive(Debug, Serialize)]
+struct SarifMessage {
+    text: String,
+}
+
+#[derive(Debug, Serialize)]
+struct SarifResult {
+    #[serde(rename = "ruleId")]
+    rule_id: Check,
+    level: String,
+    message: SarifMessage,
+    locations: Vec<SarifLocation>,
+}
+
+/// SARIF levels are "none"/"note"/"warning"/"error"; `Severity::Off` (which
+/// never reaches here since off checks don't run) maps to "none".
+fn sarif_level(severity: Severity) -> String {
+    match severity {
+        Severity::Error => "error".to_string(),
+        Severity::Warning => "warning".to_string(),
+        Severity::Off => "none".to_string(),
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct SarifDriver {
+    name: String,
+}
+
+#[derive(Debug, Serialize)]
+struct SarifTool {
+    driver: SarifDriver,
+}
+
+#[derive(Debug, Serialize)]
+struct SarifRun {
+    tool: SarifTool,
+    results: Vec<SarifResult>,
+}
+
+#[derive(Debug, Serialize)]
+struct SarifLog {
+    version: String,
+    #[serde(rename = "$schema")]
+    schema: String,
+    runs: Vec<SarifRun>,
+}
+
+#[derive(Default)]
+pub struct SarifReporter {
+    results: Vec<SarifResult>,
 }
 
-pub fn display_errors(results: &[AnalysisResult], notebook: &Notebook) {
-    for r in results.iter() {
-        if !r.pass() {
+impl Reporter for SarifReporter {
+    fn report(&mut self, results: &[AnalysisResult], notebook: &Notebook) {
+        for r in results.iter() {
             for failure in &r.failures {
-                println!(
-                    "{} <Cell: {}> {} [{}]",
-                    notebook.filename_str(),
-                    failure.cell_id,
-                    failure.description,
-                    r.check
-                )
+                self.results.push(SarifResult {
+                    rule_id: r.check.clone(),
+                    level: sarif_level(r.severity),
+                    message: SarifMessage {
+                        text: failure.description.clone(),
+                    },
+                    locations: vec![SarifLocation {
+                        physical_location: SarifPhysicalLocation {
+                            artifact_location: SarifArtifactLocation {
+                                uri: notebook.filename_str().to_string(),
+                            },
+                            region: SarifRegion {
+                                // SARIF requires startLine >= 1; cell_id is 0-based.
+                                start_line: failure.cell_id + 1,
+                            },
+                        },
+                    }],
+                });
             }
         }
     }
+
+    fn finish(&mut self) {
+        let log = SarifLog {
+            version: "2.1.0".to_string(),
+            schema: "https://raw.githubusercontent.com/oasis-tcs/sarif-spec/master/Schemata/sarif-schema-2.1.0.json".to_string(),
+            runs: vec![SarifRun {
+                tool: SarifTool {
+                    driver: SarifDriver {
+                        name: "nbsanity".to_string(),
+                    },
+                },
+                results: std::mem::take(&mut self.results),
+            }],
+        };
+        println!("{}", serde_json::to_string_pretty(&log).unwrap());
+    }
 }
 
 #[cfg(test)]
@@ -231,7 +668,7 @@ mod tests {
     #[test]
     fn analyze_returns_all_results() {
         let notebook = Notebook::new("Untitled.ipynb".into());
-        let results = analyze(&notebook, &vec![]);
+        let results = analyze(&notebook, &vec![], &HashMap::new());
         assert_eq!(results.len(), Check::all().len());
     }
 
@@ -239,9 +676,22 @@ mod tests {
     #[test]
     fn any_failed_returns_true_if_any_failed() {
         let notebook = Notebook::new("Untitled.ipynb".into());
-        let results = analyze(&notebook, &vec![]);
+        let results = analyze(&notebook, &vec![], &HashMap::new());
         assert!(any_failed(&results));
     }
+
+    #[test]
+    fn any_failed_ignores_warnings() {
+        let notebook = Notebook::new("Untitled.ipynb".into());
+        let severity: HashMap<Check, Severity> = Check::all()
+            .into_iter()
+            .map(|c| (c, Severity::Warning))
+            .collect();
+        let results = analyze(&notebook, &vec![], &severity);
+        assert!(!any_failed(&results));
+        let expected: usize = results.iter().map(|r| r.failures.len()).sum();
+        assert_eq!(warning_count(&results), expected);
+    }
     // test check empty cells true if any cell is empty
     #[test]
     fn check_empty_cells_fail_if_any_cell_is_empty() {
@@ -300,4 +750,274 @@ mod tests {
         let got = Check::NoEmptyCells(NoEmptyCells {}).check(&notebook);
         assert!(got.pass());
     }
+
+    #[test]
+    fn json_reporter_emits_one_record_per_check_with_pass_state() {
+        let notebook = Notebook::new("Untitled.ipynb".into());
+        let results = analyze(&notebook, &vec![], &HashMap::new());
+        let mut reporter = JsonReporter::default();
+        reporter.report(&results, &notebook);
+        assert_eq!(reporter.findings.len(), results.len());
+        for (finding, result) in reporter.findings.iter().zip(results.iter()) {
+            assert_eq!(finding.pass, result.pass());
+            assert_eq!(finding.failures.len(), result.failures.len());
+        }
+        assert!(reporter.findings.iter().any(|f| !f.pass));
+    }
+
+    #[test]
+    fn sarif_reporter_emits_one_result_per_failure() {
+        let notebook = Notebook::new("Untitled.ipynb".into());
+        let results = analyze(&notebook, &vec![], &HashMap::new());
+        let expected: usize = results.iter().map(|r| r.failures.len()).sum();
+        let mut reporter = SarifReporter::default();
+        reporter.report(&results, &notebook);
+        assert_eq!(reporter.results.len(), expected);
+        // SARIF requires startLine >= 1; cell_id 0 (e.g. FileNotNamedUntitled,
+        // HasTitleCell) must not be emitted as-is.
+        assert!(reporter
+            .results
+            .iter()
+            .all(|r| r.locations[0].physical_location.region.start_line >= 1));
+    }
+
+    #[test]
+    fn sarif_level_maps_severity() {
+        assert_eq!(sarif_level(Severity::Error), "error");
+        assert_eq!(sarif_level(Severity::Warning), "warning");
+        assert_eq!(sarif_level(Severity::Off), "none");
+    }
+
+    #[test]
+    fn cell_execution_is_sequential_fix_renumbers_run_cells() {
+        let mut notebook = Notebook::new("test.ipynb".into());
+        let mut first = CodeCell::default();
+        first.execution_count = Some(5);
+        let mut second = CodeCell::default();
+        second.execution_count = Some(9);
+        notebook.cells = vec![Cell::Code(first), Cell::Code(second)];
+
+        let outcome = CellExecutionIsSequential {}.fix(&mut notebook);
+        assert_eq!(outcome, FixOutcome::Applied);
+        assert_eq!(notebook.code_cells()[0].execution_count, Some(1));
+        assert_eq!(notebook.code_cells()[1].execution_count, Some(2));
+    }
+
+    #[test]
+    fn cell_execution_is_sequential_fix_leaves_never_run_cells_alone() {
+        let mut notebook = Notebook::new("test.ipynb".into());
+        let mut run = CodeCell::default();
+        run.execution_count = Some(3);
+        let mut never_run = CodeCell::default();
+        never_run.execution_count = None;
+        notebook.cells = vec![Cell::Code(run), Cell::Code(never_run)];
+
+        CellExecutionIsSequential {}.fix(&mut notebook);
+        assert_eq!(notebook.code_cells()[0].execution_count, Some(1));
+        assert_eq!(notebook.code_cells()[1].execution_count, None);
+    }
+
+    #[test]
+    fn cell_execution_is_sequential_fix_clean_when_already_sequential() {
+        let mut notebook = Notebook::new("test.ipynb".into());
+        let mut cell = CodeCell::default();
+        cell.execution_count = Some(1);
+        notebook.cells = vec![Cell::Code(cell)];
+
+        let outcome = CellExecutionIsSequential {}.fix(&mut notebook);
+        assert_eq!(outcome, FixOutcome::Clean);
+    }
+
+    #[test]
+    fn cell_execution_is_sequential_fix_agrees_with_check_when_unrun_cell_is_sandwiched() {
+        let mut notebook = Notebook::new("test.ipynb".into());
+        let mut first = CodeCell::default();
+        first.execution_count = Some(5);
+        let mut middle = CodeCell::default();
+        middle.execution_count = None;
+        let mut last = CodeCell::default();
+        last.execution_count = Some(9);
+        notebook.cells = vec![Cell::Code(first), Cell::Code(middle), Cell::Code(last)];
+        notebook.add_cell_indices();
+
+        CellExecutionIsSequential {}.fix(&mut notebook);
+        assert_eq!(notebook.code_cells()[0].execution_count, Some(1));
+        assert_eq!(notebook.code_cells()[1].execution_count, None);
+        assert_eq!(notebook.code_cells()[2].execution_count, Some(2));
+
+        // After fixing, the only remaining failure should be the cell that
+        // was never run; the renumbered cells must not also fail.
+        let result = CellExecutionIsSequential {}.check(&notebook);
+        assert_eq!(result.failures.len(), 1);
+        assert_eq!(result.failures[0].cell_id, 1);
+        assert_eq!(result.failures[0].description, "Cell was not run");
+    }
+
+    #[test]
+    fn no_empty_cells_fix_removes_blank_cells() {
+        let mut notebook = Notebook::new("test.ipynb".into());
+        let mut blank = CodeCell::default();
+        blank.source = vec!["   ".to_string()];
+        let mut real = CodeCell::default();
+        real.source = vec!["print(1)".to_string()];
+        notebook.cells = vec![Cell::Code(blank), Cell::Code(real)];
+
+        let outcome = NoEmptyCells {}.fix(&mut notebook);
+        assert_eq!(outcome, FixOutcome::Applied);
+        assert_eq!(notebook.cells.len(), 1);
+    }
+
+    #[test]
+    fn no_empty_cells_fix_clean_when_no_empty_cells() {
+        let mut notebook = Notebook::new("test.ipynb".into());
+        let mut real = CodeCell::default();
+        real.source = vec!["print(1)".to_string()];
+        notebook.cells = vec![Cell::Code(real)];
+
+        let outcome = NoEmptyCells {}.fix(&mut notebook);
+        assert_eq!(outcome, FixOutcome::Clean);
+    }
+
+    #[test]
+    fn file_not_named_untitled_fix_suggests_rename() {
+        let mut notebook = Notebook::new("Untitled.ipynb".into());
+        let outcome = FileNotNamedUntitled {}.fix(&mut notebook);
+        assert!(matches!(outcome, FixOutcome::Suggested(_)));
+    }
+
+    #[test]
+    fn file_not_named_untitled_fix_clean_when_named_well() {
+        let mut notebook = Notebook::new("analysis.ipynb".into());
+        let outcome = FileNotNamedUntitled {}.fix(&mut notebook);
+        assert_eq!(outcome, FixOutcome::Clean);
+    }
+
+    #[test]
+    fn has_title_cell_fix_clean_when_title_present() {
+        let mut notebook = Notebook::new("test.ipynb".into());
+        let title = crate::notebook::MarkdownCell {
+            id: None,
+            metadata: CodeCell::default().metadata,
+            source: vec!["'#' Title".to_string()],
+            idx: None,
+        };
+        notebook.cells = vec![Cell::Markdown(title)];
+        let outcome = HasTitleCell {}.fix(&mut notebook);
+        assert_eq!(outcome, FixOutcome::Clean);
+    }
+
+    #[test]
+    fn has_title_cell_fix_suggests_when_missing() {
+        let mut notebook = Notebook::new("test.ipynb".into());
+        let outcome = HasTitleCell {}.fix(&mut notebook);
+        assert!(matches!(outcome, FixOutcome::Suggested(_)));
+    }
+
+    #[test]
+    fn markdown_code_blocks_valid_fix_clean_when_valid() {
+        let mut notebook = Notebook::new("test.ipynb".into());
+        let outcome = MarkdownCodeBlocksValid {}.fix(&mut notebook);
+        assert_eq!(outcome, FixOutcome::Clean);
+    }
+
+    #[test]
+    fn parse_fence_info_plain_language() {
+        assert_eq!(parse_fence_info("python"), (Some("python".to_string()), false));
+    }
+
+    #[test]
+    fn parse_fence_info_is_case_insensitive() {
+        assert_eq!(parse_fence_info("PYTHON"), (Some("python".to_string()), false));
+    }
+
+    #[test]
+    fn parse_fence_info_ignore_marker() {
+        assert_eq!(parse_fence_info("ignore"), (None, true));
+        assert_eq!(parse_fence_info("python ignore"), (Some("python".to_string()), true));
+    }
+
+    #[test]
+    fn parse_fence_info_empty() {
+        assert_eq!(parse_fence_info(""), (None, false));
+    }
+
+    #[test]
+    fn markdown_code_blocks_valid_flags_unterminated_fence() {
+        let mut notebook = Notebook::new("test.ipynb".into());
+        let cell = crate::notebook::MarkdownCell {
+            id: None,
+            metadata: CodeCell::default().metadata,
+            source: vec!["```python".to_string(), "print(1)".to_string()],
+            idx: Some(0),
+        };
+        notebook.cells = vec![Cell::Markdown(cell)];
+        let result = Check::MarkdownCodeBlocksValid(MarkdownCodeBlocksValid).check(&notebook);
+        assert!(result.fail());
+        assert!(result.failures[0].description.contains("Unterminated"));
+    }
+
+    #[test]
+    fn markdown_code_blocks_valid_passes_terminated_fence() {
+        let mut notebook = Notebook::new("test.ipynb".into());
+        let cell = crate::notebook::MarkdownCell {
+            id: None,
+            metadata: CodeCell::default().metadata,
+            source: vec![
+                "```python".to_string(),
+                "print(1)".to_string(),
+                "```".to_string(),
+            ],
+            idx: Some(0),
+        };
+        notebook.cells = vec![Cell::Markdown(cell)];
+        let result = Check::MarkdownCodeBlocksValid(MarkdownCodeBlocksValid).check(&notebook);
+        assert!(result.pass());
+    }
+
+    #[test]
+    fn markdown_code_blocks_valid_flags_language_mismatch() {
+        let json = r#"{
+            "cells": [{
+                "cell_type": "markdown",
+                "id": null,
+                "metadata": {},
+                "source": ["```rust", "fn main() {}", "```"]
+            }],
+            "nbformat": 4,
+            "nbformat_minor": 5,
+            "metadata": {"language_info": {"name": "python"}}
+        }"#;
+        let notebook: Notebook = serde_json::from_str(json).unwrap();
+        let result = Check::MarkdownCodeBlocksValid(MarkdownCodeBlocksValid).check(&notebook);
+        assert!(result.fail());
+        assert!(result.failures[0].description.contains("rust"));
+    }
+
+    #[test]
+    fn markdown_code_blocks_valid_ignores_explicitly_marked_fence() {
+        let json = r#"{
+            "cells": [{
+                "cell_type": "markdown",
+                "id": null,
+                "metadata": {},
+                "source": ["```rust ignore", "fn main() {}", "```"]
+            }],
+            "nbformat": 4,
+            "nbformat_minor": 5,
+            "metadata": {"language_info": {"name": "python"}}
+        }"#;
+        let notebook: Notebook = serde_json::from_str(json).unwrap();
+        let result = Check::MarkdownCodeBlocksValid(MarkdownCodeBlocksValid).check(&notebook);
+        assert!(result.pass());
+    }
+
+    #[test]
+    fn fix_all_skips_disabled_checks() {
+        let mut notebook = Notebook::new("Untitled.ipynb".into());
+        let outcomes = fix_all(&mut notebook, &[Check::FileNotNamedUntitled(FileNotNamedUntitled)]);
+        assert!(outcomes
+            .iter()
+            .all(|(c, _)| !matches!(c, Check::FileNotNamedUntitled(_))));
+        assert_eq!(outcomes.len(), Check::all().len() - 1);
+    }
 }
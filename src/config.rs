@@ -1,11 +1,26 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 #[derive(Deserialize, Debug, Serialize)]
 pub struct Config {
     pub root: Option<String>,
+    /// Additional root directories to search, on top of `root` (or `.` if
+    /// `root` is unset). `rglob` unions and de-dupes notebooks found under
+    /// every root, the same way `include`/`exclude` patterns are combined.
+    pub roots: Option<Vec<String>>,
     pub disable: Option<Vec<String>>,
+    /// Glob patterns (relative to `root`) of notebooks to lint. Defaults
+    /// to every `*.ipynb` under `root` when unset.
+    pub include: Option<Vec<String>>,
+    /// Glob patterns (relative to `root`) of notebooks to skip, e.g.
+    /// `"**/checkpoints/**"`.
+    pub exclude: Option<Vec<String>>,
+    /// Check name -> "error" | "warning" | "off". Parsed as raw strings
+    /// here; `main` resolves and validates them against `Check`/`Severity`
+    /// so it can suggest corrections for typos via `find_closest`.
+    pub severity: Option<HashMap<String, String>>,
 }
 
 #[derive(Deserialize, Debug, Serialize)]
@@ -28,7 +43,11 @@ impl Config {
             }
             Err(_e) => Config {
                 root: None,
+                roots: None,
                 disable: Some(Vec::new()),
+                include: None,
+                exclude: None,
+                severity: None,
             },
         };
         return config;
@@ -40,6 +59,18 @@ impl Config {
             None => Path::new("."),
         }
     }
+
+    /// Every root to search: `root_path()` plus any extra `roots`,
+    /// de-duplicated.
+    pub fn root_paths(&self) -> Vec<PathBuf> {
+        let mut paths = vec![self.root_path().to_path_buf()];
+        if let Some(extra) = &self.roots {
+            paths.extend(extra.iter().map(PathBuf::from));
+        }
+        paths.sort();
+        paths.dedup();
+        paths
+    }
 }
 
 #[cfg(test)]
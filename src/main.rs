@@ -2,10 +2,20 @@ mod checks;
 mod config;
 mod notebook;
 use anyhow::Result;
-use checks::{analyze, any_failed, display_errors, find_closest, Check};
+use checks::{
+    analyze, any_failed, find_closest, fix_all, reporter_for, warning_count, AnalysisResult,
+    Check, FixOutcome, ReportFormat, Reporter, Severity,
+};
 use config::Config;
-use notebook::Notebook;
+use notebook::{Notebook, NotebookFilter};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use rayon::prelude::*;
 use serde_json::Error;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::mpsc::channel;
+use std::time::Duration;
 use structopt::StructOpt;
 
 #[derive(StructOpt, Debug)]
@@ -17,21 +27,208 @@ struct CliOpts {
     /// Don't output to stdout if successfull
     #[structopt(short, long)]
     quiet: bool,
+
+    /// Re-run checks whenever a notebook under the root changes
+    #[structopt(short, long)]
+    watch: bool,
+
+    /// Output format for results
+    #[structopt(long, default_value = "human")]
+    format: ReportFormat,
+
+    /// Rewrite notebooks in place to repair anything with an automatic fix
+    #[structopt(long)]
+    fix: bool,
+
+    /// Fail the run once warning-severity findings exceed this count
+    #[structopt(long)]
+    max_warnings: Option<usize>,
+}
+
+/// Look up an unknown check name and exit with a "did you mean"
+/// suggestion, matching the existing `disable = [...]` validation.
+fn fail_unknown_check(name: &str) -> ! {
+    let closest = find_closest(name.to_string());
+    println!("Unknown check '{}', did you mean {} ?", name, closest.to_str());
+    std::process::exit(1);
+}
+
+/// Parse and validate the `[tool.nbsanity] severity` table. A check whose
+/// severity is `off` is folded into `disabled` so it's skipped entirely,
+/// the same as `disable = [...]`.
+fn resolve_severity(raw: HashMap<String, String>, disabled: &mut Vec<Check>) -> HashMap<Check, Severity> {
+    let mut resolved = HashMap::new();
+    for (name, severity_str) in raw {
+        let check = Check::from_str(&name).unwrap_or_else(|_| fail_unknown_check(&name));
+        let severity = Severity::from_str(&severity_str).unwrap_or_else(|_| {
+            println!(
+                "Unknown severity '{}' for check {}, expected error, warning, or off",
+                severity_str, name
+            );
+            std::process::exit(1);
+        });
+        match severity {
+            Severity::Off => disabled.push(check),
+            _ => {
+                resolved.insert(check, severity);
+            }
+        }
+    }
+    resolved
+}
+
+/// Apply every fixable check to each notebook, rewriting it to disk if
+/// anything changed and printing suggestions for checks that can't be
+/// fixed automatically.
+fn apply_fixes(paths: &[PathBuf], disabled: &[Check]) {
+    for path in paths {
+        let mut notebook = match Notebook::from_file(path.clone()) {
+            Ok(n) => n,
+            Err(e) => {
+                eprintln!("{}: {}", path.display(), e);
+                continue;
+            }
+        };
+        notebook.add_cell_indices();
+        let outcomes = fix_all(&mut notebook, disabled);
+
+        let mut changed = false;
+        for (check, outcome) in outcomes {
+            match outcome {
+                FixOutcome::Applied => changed = true,
+                FixOutcome::Suggested(message) => {
+                    println!("{} [{}] {}", notebook.filename_str(), check, message)
+                }
+                FixOutcome::Clean => {}
+            }
+        }
+
+        if changed {
+            match notebook.write_to_file() {
+                Ok(()) => println!("{} \u{1F527} fixed", notebook.filename_str()),
+                Err(e) => eprintln!("{}: failed to write fixes: {}", notebook.filename_str(), e),
+            }
+        }
+    }
+}
+
+/// Parse and analyze every notebook path in parallel, then report results
+/// in a deterministic order (sorted by filename) regardless of which
+/// notebook happened to finish analysis first.
+/// Returns whether any `error`-severity check failed and the total count
+/// of `warning`-severity findings (for `--max-warnings`).
+fn lint(
+    paths: &[PathBuf],
+    disabled: &[Check],
+    severity: &HashMap<Check, Severity>,
+    reporter: &mut dyn Reporter,
+) -> (bool, usize) {
+    let mut results: Vec<(Notebook, Vec<AnalysisResult>)> = paths
+        .par_iter()
+        .filter_map(|p| match Notebook::from_file(p.clone()) {
+            Ok(notebook) => Some(notebook),
+            Err(e) => {
+                eprintln!("{}: {}", p.display(), e);
+                None
+            }
+        })
+        .map(|mut notebook| {
+            notebook.add_cell_indices();
+            let analysis = analyze(&notebook, disabled, severity);
+            (notebook, analysis)
+        })
+        .collect();
+    results.sort_by(|(a, _), (b, _)| a.filename_str().cmp(b.filename_str()));
+
+    let mut global_any_failed = false;
+    let mut total_warnings = 0;
+    for (notebook, analysis) in results.iter() {
+        if any_failed(analysis) {
+            global_any_failed = true;
+        }
+        total_warnings += warning_count(analysis);
+        reporter.report(analysis, notebook);
+    }
+    reporter.finish();
+    (global_any_failed, total_warnings)
+}
+
+/// Watch `conf`'s root for notebook changes, re-linting only the
+/// notebook(s) that actually changed after each debounced batch of
+/// filesystem events. Changed paths are filtered through the same
+/// `NotebookFilter` (include/exclude globs + extension) that `rglob`
+/// uses for the initial discovery pass, so `--watch` honors them too.
+fn watch(
+    conf: &Config,
+    disabled: &[Check],
+    severity: &HashMap<Check, Severity>,
+    format: ReportFormat,
+    quiet: bool,
+) -> Result<(), Error> {
+    let root = conf.root_path();
+    let filter = NotebookFilter::new(conf).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    });
+
+    let (tx, rx) = channel();
+    let mut watcher: RecommendedWatcher =
+        notify::recommended_watcher(tx).expect("failed to start file watcher");
+    watcher
+        .watch(root, RecursiveMode::Recursive)
+        .expect("failed to watch root path");
+
+    println!("Watching {} for notebook changes...", root.display());
+    loop {
+        let first = match rx.recv() {
+            Ok(event) => event,
+            Err(_) => return Ok(()),
+        };
+        // Debounce: drain any events that land in the next moment so a
+        // single save (which often fires several fs events) triggers one
+        // re-lint rather than several.
+        let mut events = vec![first];
+        while let Ok(event) = rx.recv_timeout(Duration::from_millis(200)) {
+            events.push(event);
+        }
+
+        let mut changed: Vec<_> = events
+            .into_iter()
+            .filter_map(Result::ok)
+            .flat_map(|e| e.paths)
+            .filter(|p| filter.matches(p))
+            .collect();
+        changed.sort();
+        changed.dedup();
+        if changed.is_empty() {
+            continue;
+        }
+
+        print!("\x1B[2J\x1B[1;1H"); // clear the screen between runs
+        let mut reporter = reporter_for(format, quiet);
+        lint(&changed, disabled, severity, reporter.as_mut());
+    }
 }
 
 fn main() -> Result<(), Error> {
     let opts = CliOpts::from_args();
     let conf = Config::build();
-    let mut notebooks = Notebook::rglob(conf.root_path()).unwrap();
-    let mut global_any_failed = false;
+    let paths = match Notebook::rglob(&conf) {
+        Ok(paths) => paths,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
     let (disabled, errors): (Vec<_>, Vec<_>) = conf
         .disable
+        .clone()
         .unwrap_or_default()
         .iter()
         .map(|s| Check::from_str(s))
         .partition(Result::is_ok);
 
-    let disabled: Vec<_> = disabled.into_iter().map(Result::unwrap).collect();
+    let mut disabled: Vec<_> = disabled.into_iter().map(Result::unwrap).collect();
     let errors: Vec<_> = errors.into_iter().map(Result::unwrap_err).collect();
     if !errors.is_empty() {
         for e in errors {
@@ -45,20 +242,27 @@ fn main() -> Result<(), Error> {
         std::process::exit(1);
     }
 
-    for notebook in notebooks.iter_mut() {
-        notebook.add_cell_indices();
-        let analysis = analyze(notebook, &disabled);
-        let failed = any_failed(&analysis);
-        if failed {
+    let severity = resolve_severity(conf.severity.clone().unwrap_or_default(), &mut disabled);
+
+    if opts.fix {
+        apply_fixes(&paths, &disabled);
+    }
+
+    let mut reporter = reporter_for(opts.format, opts.quiet);
+    let (mut global_any_failed, total_warnings) =
+        lint(&paths, &disabled, &severity, reporter.as_mut());
+    if let Some(max_warnings) = opts.max_warnings {
+        if total_warnings > max_warnings {
             global_any_failed = true;
-            display_errors(&analysis, notebook);
-        } else if !opts.quiet {
-            println!("{} \u{2705}", notebook.filename_str());
         }
     }
 
+    if opts.watch {
+        return watch(&conf, &disabled, &severity, opts.format, opts.quiet);
+    }
+
     match global_any_failed {
-        false => std::process::exit(1),
-        true => std::process::exit(0),
+        false => std::process::exit(0),
+        true => std::process::exit(1),
     }
 }
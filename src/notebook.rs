@@ -1,15 +1,96 @@
-use glob::glob;
+use crate::config::Config;
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use ignore::WalkBuilder;
 use serde::{Deserialize, Serialize};
-use serde_json::Error;
+use serde_json::{Error, Value};
+use std::collections::HashMap;
 use std::default::Default;
+use std::fmt;
 use std::fs;
 use std::path::{Path, PathBuf};
 
+/// Errors from discovering notebooks (an invalid include/exclude pattern),
+/// as opposed to parsing one (see `Notebook::from_file`'s `serde_json::Error`).
+#[derive(Debug)]
+pub enum GlobError {
+    Pattern(String),
+}
+
+impl fmt::Display for GlobError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GlobError::Pattern(msg) => write!(f, "invalid include/exclude pattern: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for GlobError {}
+
+fn build_glob_set(patterns: &[String]) -> Result<GlobSet, GlobError> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let glob = Glob::new(pattern).map_err(|e| GlobError::Pattern(e.to_string()))?;
+        builder.add(glob);
+    }
+    builder.build().map_err(|e| GlobError::Pattern(e.to_string()))
+}
+
+fn is_ipynb(path: &Path) -> bool {
+    path.extension().and_then(|e| e.to_str()) == Some("ipynb")
+}
+
+/// Precomputed include/exclude/extension matcher for `Config`, shared by
+/// `Notebook::rglob` (initial discovery) and `--watch` (per-event
+/// filtering) so both honor the same rules instead of `watch` only
+/// checking the file extension.
+pub struct NotebookFilter {
+    roots: Vec<PathBuf>,
+    include: GlobSet,
+    exclude: GlobSet,
+}
+
+impl NotebookFilter {
+    pub fn new(conf: &Config) -> Result<NotebookFilter, GlobError> {
+        let include = build_glob_set(
+            &conf
+                .include
+                .clone()
+                .unwrap_or_else(|| vec!["**/*.ipynb".to_string()]),
+        )?;
+        let exclude = build_glob_set(&conf.exclude.clone().unwrap_or_default())?;
+        Ok(NotebookFilter {
+            roots: conf.root_paths(),
+            include,
+            exclude,
+        })
+    }
+
+    pub fn matches(&self, path: &Path) -> bool {
+        if !is_ipynb(path) {
+            return false;
+        }
+        self.roots.iter().any(|root| {
+            // A root pointed directly at a notebook file (rather than a
+            // directory) always matches, the same as rglob's non-dir case.
+            if path == root {
+                return true;
+            }
+            let relative = path.strip_prefix(root).unwrap_or(path);
+            self.include.is_match(relative) && !self.exclude.is_match(relative)
+        })
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct CellOutput {
     name: Option<String>,
     output_type: Option<String>,
     text: Option<Vec<String>>,
+    // Catch-all for fields nbsanity doesn't model (e.g. `data`,
+    // `execution_count`, `ename`/`evalue`/`traceback`), so fixing a
+    // notebook and writing it back doesn't silently drop output data.
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -26,6 +107,10 @@ pub struct CellMetaData {
     // TODO scrolled
     name: Option<String>,
     tags: Option<Vec<String>>,
+    // Catch-all for fields nbsanity doesn't model, so fixing a notebook
+    // and writing it back doesn't silently drop metadata we don't parse.
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -36,6 +121,9 @@ pub struct CodeCell {
     pub outputs: Vec<CellOutput>,
     // Source as array of lines
     pub source: Vec<String>,
+    /// Internal bookkeeping set by `add_cell_indices`, not part of the
+    /// `.ipynb` schema — never written back to disk.
+    #[serde(skip_serializing)]
     pub idx: Option<usize>,
 }
 
@@ -49,6 +137,7 @@ impl Default for CodeCell {
                 collapsed: None,
                 name: None,
                 tags: None,
+                extra: HashMap::new(),
             },
             execution_count: Some(1),
             outputs: vec![],
@@ -63,6 +152,9 @@ pub struct MarkdownCell {
     pub id: Option<String>,
     pub metadata: CellMetaData,
     pub source: Vec<String>,
+    /// Internal bookkeeping set by `add_cell_indices`, not part of the
+    /// `.ipynb` schema — never written back to disk.
+    #[serde(skip_serializing)]
     pub idx: Option<usize>,
 }
 
@@ -140,6 +232,10 @@ pub struct NotebookMeta {
     title: Option<String>,
     vscode: Option<VsCode>,
     authors: Option<Vec<Author>>,
+    // Catch-all for fields nbsanity doesn't model, so fixing a notebook
+    // and writing it back doesn't silently drop metadata we don't parse.
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -165,20 +261,37 @@ impl Notebook {
         notebook
     }
 
-    pub fn rglob(root: &Path) -> Option<Vec<Notebook>> {
-        if root.is_dir() {
-            let root_str = root.to_str()?;
-            let glob_str = format!("{}/**/*.ipynb", root_str);
-            let files = glob(&glob_str).unwrap();
-            let result: Vec<Notebook> = files
-                .map(|p| Notebook::from_file(p.unwrap()).unwrap())
-                .collect();
-            Some(result)
-        } else if root.extension().unwrap_or_else(|| "".as_ref()) == ".ipynb" {
-            return Some(vec![Notebook::from_file(root.to_path_buf()).unwrap()]);
-        } else {
-            return Some(vec![]);
+    /// Find every notebook under `conf`'s root(s) without parsing any of
+    /// them yet, so callers can parse (and analyze) the results in
+    /// parallel. Honors `conf.include`/`conf.exclude` glob patterns and
+    /// `.gitignore`, unions and de-duplicates paths across every root
+    /// (see `Config::root_paths`), and filters by a proper `ipynb`
+    /// extension check rather than comparing against the dotted `".ipynb"`.
+    pub fn rglob(conf: &Config) -> Result<Vec<PathBuf>, GlobError> {
+        let filter = NotebookFilter::new(conf)?;
+
+        let mut found: Vec<PathBuf> = Vec::new();
+        for root in &filter.roots {
+            if !root.is_dir() {
+                if filter.matches(root) {
+                    found.push(root.clone());
+                }
+                continue;
+            }
+
+            found.extend(
+                WalkBuilder::new(root)
+                    .git_ignore(true)
+                    .build()
+                    .filter_map(Result::ok)
+                    .map(|entry| entry.into_path())
+                    .filter(|p| filter.matches(p)),
+            );
         }
+
+        found.sort();
+        found.dedup();
+        Ok(found)
     }
 
     pub fn from_file(path: PathBuf) -> Result<Notebook, Error> {
@@ -188,6 +301,15 @@ impl Notebook {
         Ok(notebook)
     }
 
+    /// Write this notebook back to `self.filename`, e.g. after applying a
+    /// `Fix`. Unknown metadata fields round-trip thanks to the `#[serde(flatten)]`
+    /// catch-alls on `CellMetaData`/`NotebookMeta`.
+    pub fn write_to_file(&self) -> std::io::Result<()> {
+        let path = self.filename.as_ref().expect("notebook has no filename");
+        let contents = serde_json::to_string_pretty(self).expect("failed to serialize notebook");
+        fs::write(path, contents)
+    }
+
     pub fn filename_str(&self) -> &str {
         match &self.filename {
             Some(f) => f.to_str().unwrap_or("???"),
@@ -210,4 +332,210 @@ impl Notebook {
     pub fn markdown_cells(&self) -> Vec<&MarkdownCell> {
         extract_markdown_cells(&self.cells)
     }
+
+    /// The kernel language this notebook declares, e.g. `"python"`.
+    pub fn language_name(&self) -> Option<&str> {
+        self.metadata
+            .language_info
+            .as_ref()
+            .map(|l| l.name.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("nbsanity-test-{}-{}.ipynb", std::process::id(), name))
+    }
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("nbsanity-test-{}-{}", std::process::id(), name))
+    }
+
+    fn config_with_root(root: &Path) -> Config {
+        Config {
+            root: Some(root.to_str().unwrap().to_string()),
+            roots: None,
+            disable: None,
+            include: None,
+            exclude: None,
+            severity: None,
+        }
+    }
+
+    #[test]
+    fn is_ipynb_requires_proper_extension() {
+        assert!(is_ipynb(Path::new("notebook.ipynb")));
+        assert!(!is_ipynb(Path::new("notebook.ipynb.bak")));
+        assert!(!is_ipynb(Path::new("notebook")));
+    }
+
+    #[test]
+    fn build_glob_set_matches_patterns() {
+        let set = build_glob_set(&["notebooks/**".to_string()]).unwrap();
+        assert!(set.is_match(Path::new("notebooks/a.ipynb")));
+        assert!(!set.is_match(Path::new("other/a.ipynb")));
+    }
+
+    #[test]
+    fn build_glob_set_rejects_invalid_pattern() {
+        assert!(build_glob_set(&["[".to_string()]).is_err());
+    }
+
+    #[test]
+    fn rglob_finds_notebooks_recursively() {
+        let root = scratch_dir("rglob-basic");
+        fs::create_dir_all(root.join("sub")).unwrap();
+        fs::write(root.join("a.ipynb"), "{}").unwrap();
+        fs::write(root.join("sub/b.ipynb"), "{}").unwrap();
+        fs::write(root.join("readme.txt"), "not a notebook").unwrap();
+
+        let found = Notebook::rglob(&config_with_root(&root)).unwrap();
+        fs::remove_dir_all(&root).ok();
+
+        assert_eq!(found.len(), 2);
+        assert!(found.iter().all(|p| is_ipynb(p)));
+    }
+
+    #[test]
+    fn rglob_include_pattern_is_relative_to_root() {
+        let root = scratch_dir("rglob-include");
+        fs::create_dir_all(root.join("notebooks")).unwrap();
+        fs::create_dir_all(root.join("other")).unwrap();
+        fs::write(root.join("notebooks/a.ipynb"), "{}").unwrap();
+        fs::write(root.join("other/b.ipynb"), "{}").unwrap();
+
+        let mut conf = config_with_root(&root);
+        conf.include = Some(vec!["notebooks/**".to_string()]);
+        let found = Notebook::rglob(&conf).unwrap();
+        fs::remove_dir_all(&root).ok();
+
+        assert_eq!(found.len(), 1);
+        assert!(found[0].ends_with("notebooks/a.ipynb"));
+    }
+
+    #[test]
+    fn rglob_exclude_pattern_is_relative_to_root() {
+        let root = scratch_dir("rglob-exclude");
+        fs::create_dir_all(root.join("checkpoints")).unwrap();
+        fs::write(root.join("a.ipynb"), "{}").unwrap();
+        fs::write(root.join("checkpoints/a.ipynb"), "{}").unwrap();
+
+        let mut conf = config_with_root(&root);
+        conf.exclude = Some(vec!["checkpoints/**".to_string()]);
+        let found = Notebook::rglob(&conf).unwrap();
+        fs::remove_dir_all(&root).ok();
+
+        assert_eq!(found.len(), 1);
+        assert!(found[0].ends_with("a.ipynb"));
+        assert!(!found[0].to_str().unwrap().contains("checkpoints"));
+    }
+
+    #[test]
+    fn rglob_unions_multiple_roots() {
+        let root_a = scratch_dir("rglob-multiroot-a");
+        let root_b = scratch_dir("rglob-multiroot-b");
+        fs::create_dir_all(&root_a).unwrap();
+        fs::create_dir_all(&root_b).unwrap();
+        fs::write(root_a.join("a.ipynb"), "{}").unwrap();
+        fs::write(root_b.join("b.ipynb"), "{}").unwrap();
+
+        let mut conf = config_with_root(&root_a);
+        conf.roots = Some(vec![root_b.to_str().unwrap().to_string()]);
+        let found = Notebook::rglob(&conf).unwrap();
+        fs::remove_dir_all(&root_a).ok();
+        fs::remove_dir_all(&root_b).ok();
+
+        assert_eq!(found.len(), 2);
+        assert!(found.iter().any(|p| p.ends_with("a.ipynb")));
+        assert!(found.iter().any(|p| p.ends_with("b.ipynb")));
+    }
+
+    #[test]
+    fn notebook_filter_matches_honors_extension_and_exclude() {
+        let root = scratch_dir("filter-matches");
+        fs::create_dir_all(root.join("checkpoints")).unwrap();
+
+        let mut conf = config_with_root(&root);
+        conf.exclude = Some(vec!["checkpoints/**".to_string()]);
+        let filter = NotebookFilter::new(&conf).unwrap();
+
+        assert!(filter.matches(&root.join("a.ipynb")));
+        assert!(!filter.matches(&root.join("a.txt")));
+        assert!(!filter.matches(&root.join("checkpoints/a.ipynb")));
+
+        fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn write_to_file_does_not_serialize_idx() {
+        let path = scratch_path("write-idx");
+        let mut notebook = Notebook::new(path.to_str().unwrap());
+        notebook.cells = vec![Cell::Code(CodeCell::default())];
+        notebook.add_cell_indices();
+
+        notebook.write_to_file().unwrap();
+        let written = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).ok();
+
+        assert!(!written.contains("\"idx\""));
+    }
+
+    #[test]
+    fn write_to_file_round_trip_preserves_unknown_metadata() {
+        let path = scratch_path("write-roundtrip");
+        fs::write(
+            &path,
+            r#"{
+                "cells": [],
+                "nbformat": 4,
+                "nbformat_minor": 5,
+                "metadata": {"some_future_field": "keep me"}
+            }"#,
+        )
+        .unwrap();
+
+        let notebook = Notebook::from_file(path.clone()).unwrap();
+        notebook.write_to_file().unwrap();
+        let written = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).ok();
+
+        assert!(written.contains("keep me"));
+    }
+
+    #[test]
+    fn write_to_file_round_trip_preserves_unknown_output_fields() {
+        let path = scratch_path("write-roundtrip-output");
+        fs::write(
+            &path,
+            r#"{
+                "cells": [{
+                    "cell_type": "code",
+                    "id": null,
+                    "metadata": {},
+                    "execution_count": 1,
+                    "outputs": [{
+                        "output_type": "execute_result",
+                        "execution_count": 1,
+                        "data": {"text/plain": ["42"]}
+                    }],
+                    "source": ["40 + 2"]
+                }],
+                "nbformat": 4,
+                "nbformat_minor": 5,
+                "metadata": {}
+            }"#,
+        )
+        .unwrap();
+
+        let notebook = Notebook::from_file(path.clone()).unwrap();
+        notebook.write_to_file().unwrap();
+        let written = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).ok();
+
+        assert!(written.contains("\"data\""));
+        assert!(written.contains("text/plain"));
+    }
 }